@@ -36,13 +36,76 @@ export const MEDIA_EXTENSIONS = {
     "dpx",
     "tga",
   ],
-  project: ["prproj", "mogrt", "xml", "aaf", "edl"],
+  cameraRaw: [
+    "arw",
+    "cr2",
+    "cr3",
+    "crw",
+    "dng",
+    "erf",
+    "iiq",
+    "kdc",
+    "mrw",
+    "nef",
+    "nrw",
+    "orf",
+    "pef",
+    "raf",
+    "raw",
+    "rw2",
+    "rwl",
+    "sr2",
+    "srf",
+    "srw",
+    "x3f",
+  ],
+  project: ["prproj", "mogrt", "xml", "aaf", "edl", "aep"],
+  externalProject: ["aegraphic", "sesx", "drp", "fcpxml", "otio"],
+  caption: ["srt", "vtt", "scc", "stl", "itt", "mcc"],
+  lut: ["cube", "3dl", "look", "icc"],
+  cache: ["cfa", "pek", "ims", "prmdc"],
 } as const;
 
 export type MediaCategory = keyof typeof MEDIA_EXTENSIONS;
 
+/**
+ * Categories that are recognized but not treated as media unless a scan
+ * enables them, e.g. "cache" for monitoring Premiere's generated files, or
+ * "cameraRaw" and "externalProject" for files Premiere cannot import.
+ */
+export const OPT_IN_CATEGORIES: readonly MediaCategory[] = [
+  "cameraRaw",
+  "externalProject",
+  "lut",
+  "cache",
+];
+
+/**
+ * User-defined categories keyed by name, e.g. `{ documents: ["pdf", "csv"] }`.
+ * Extensions match case-insensitively, with or without a leading dot.
+ */
+export type CustomCategories = Record<string, readonly string[]>;
+
+/**
+ * A built-in media category or the name of a user-defined one.
+ */
+export type FileCategory = MediaCategory | (string & {});
+
+/**
+ * Normalizes a user-supplied extension, so ".MOV" and "mov" compare equal.
+ * @param extension - Extension with or without a leading dot
+ * @returns The lowercase extension without a leading dot
+ */
+export const normalizeExtension = (extension: string): string => {
+  return extension.trim().replace(/^\./, "").toLowerCase();
+};
+
 const ALL_MEDIA_EXTENSIONS_SET = new Set<string>(
-  Object.values(MEDIA_EXTENSIONS).flat(),
+  Object.entries(MEDIA_EXTENSIONS)
+    .filter(
+      ([category]) => !OPT_IN_CATEGORIES.includes(category as MediaCategory),
+    )
+    .flatMap(([, extensions]) => extensions),
 );
 
 export const ALL_MEDIA_EXTENSIONS: readonly string[] = Array.from(
@@ -52,23 +115,48 @@ export const ALL_MEDIA_EXTENSIONS: readonly string[] = Array.from(
 /**
  * Checks if a filename has a recognized media file extension.
  * @param filename - The filename or path to check
+ * @param customCategories - Additional user-defined categories to match
+ * @param enabledCategories - Opt-in categories to treat as media
  * @returns true if the file has a media extension, false otherwise
  */
-export const isMediaFile = (filename: string): boolean => {
+export const isMediaFile = (
+  filename: string,
+  customCategories: CustomCategories = {},
+  enabledCategories: readonly MediaCategory[] = [],
+): boolean => {
   const lastDotIndex = filename.lastIndexOf(".");
   if (lastDotIndex === -1 || lastDotIndex === filename.length - 1) {
     return false;
   }
   const extension = filename.slice(lastDotIndex + 1).toLowerCase();
-  return ALL_MEDIA_EXTENSIONS_SET.has(extension);
+  if (ALL_MEDIA_EXTENSIONS_SET.has(extension)) {
+    return true;
+  }
+  const enabled = enabledCategories.some((category) =>
+    (MEDIA_EXTENSIONS[category] as readonly string[]).includes(extension),
+  );
+  if (enabled) {
+    return true;
+  }
+  return Object.values(customCategories).some((extensions) =>
+    extensions.some((custom) => normalizeExtension(custom) === extension),
+  );
 };
 
 /**
  * Gets the media category for a given filename.
+ * Built-in categories take precedence over user-defined ones, except opt-in
+ * categories that are not enabled, which are skipped entirely.
  * @param filename - The filename or path to check
- * @returns The media category or null if not a media file
+ * @param customCategories - Additional user-defined categories to match
+ * @param enabledCategories - Opt-in categories to consider
+ * @returns The category name or null if not a media file
  */
-export const getMediaCategory = (filename: string): MediaCategory | null => {
+export const getMediaCategory = (
+  filename: string,
+  customCategories: CustomCategories = {},
+  enabledCategories: readonly MediaCategory[] = [],
+): FileCategory | null => {
   const lastDotIndex = filename.lastIndexOf(".");
   if (lastDotIndex === -1 || lastDotIndex === filename.length - 1) {
     return null;
@@ -76,9 +164,137 @@ export const getMediaCategory = (filename: string): MediaCategory | null => {
   const extension = filename.slice(lastDotIndex + 1).toLowerCase();
 
   for (const [category, extensions] of Object.entries(MEDIA_EXTENSIONS)) {
+    const mediaCategory = category as MediaCategory;
+    if (
+      OPT_IN_CATEGORIES.includes(mediaCategory) &&
+      !enabledCategories.includes(mediaCategory)
+    ) {
+      continue;
+    }
     if ((extensions as readonly string[]).includes(extension)) {
-      return category as MediaCategory;
+      return mediaCategory;
+    }
+  }
+  for (const [category, extensions] of Object.entries(customCategories)) {
+    if (extensions.some((custom) => normalizeExtension(custom) === extension)) {
+      return category;
     }
   }
   return null;
 };
+
+/**
+ * Applications that project files most likely belong to, keyed by extension.
+ * Interchange formats (xml, aaf, edl) are omitted since any NLE may write them.
+ */
+export const PROJECT_APPLICATIONS: Readonly<Record<string, string>> = {
+  prproj: "Premiere Pro",
+  mogrt: "Premiere Pro",
+  aep: "After Effects",
+  aegraphic: "After Effects",
+  sesx: "Audition",
+  drp: "DaVinci Resolve",
+  fcpxml: "Final Cut Pro",
+  otio: "OpenTimelineIO",
+};
+
+/**
+ * Gets the application a project file most likely belongs to.
+ * @param filename - The filename or path to check
+ * @param applications - Extension to application overrides, checked first
+ * @returns The application name or null if unknown
+ */
+export const getProjectApplication = (
+  filename: string,
+  applications: Readonly<Record<string, string>> = {},
+): string | null => {
+  const lastDotIndex = filename.lastIndexOf(".");
+  if (lastDotIndex === -1 || lastDotIndex === filename.length - 1) {
+    return null;
+  }
+  const extension = filename.slice(lastDotIndex + 1).toLowerCase();
+  for (const [custom, application] of Object.entries(applications)) {
+    if (normalizeExtension(custom) === extension) {
+      return application;
+    }
+  }
+  return PROJECT_APPLICATIONS[extension] ?? null;
+};
+
+/**
+ * Filename patterns left behind by cloud sync tools when copies conflict.
+ * Generic duplicate suffixes like "Clip (1).mov" are left out on purpose,
+ * since ordinary media is often named that way.
+ */
+export const SYNC_CONFLICT_PATTERNS: readonly RegExp[] = [
+  /\([^)]*conflicted copy[^)]*\)/i, // Dropbox
+  /\.sync-conflict-/i, // Syncthing
+];
+
+/**
+ * Checks if a filename looks like a cloud sync conflict copy.
+ * @param filename - The filename to check
+ * @param patterns - Patterns to match, defaults to the built-in set
+ * @returns true if any pattern matches
+ */
+export const isSyncConflictFile = (
+  filename: string,
+  patterns: readonly RegExp[] = SYNC_CONFLICT_PATTERNS,
+): boolean => {
+  return patterns.some((pattern) => {
+    // Global and sticky patterns resume from lastIndex, so start each test over
+    pattern.lastIndex = 0;
+    return pattern.test(filename);
+  });
+};
+
+/**
+ * Folders where Premiere writes the files of the opt-in "cache" category.
+ */
+export const MEDIA_CACHE_DIRECTORIES: readonly string[] = [
+  "Media Cache",
+  "Media Cache Files",
+  "Peak Files",
+];
+
+/**
+ * Directory names skipped during scans, so a whole media drive can be watched
+ * without descending into OS metadata, trash, caches, or tooling folders.
+ */
+export const DEFAULT_EXCLUDED_DIRECTORIES: readonly string[] = [
+  // macOS
+  ".Spotlight-V100",
+  ".Trashes",
+  ".fseventsd",
+  ".TemporaryItems",
+  ".DocumentRevisions-V100",
+  // Windows
+  "$RECYCLE.BIN",
+  "System Volume Information",
+  // Adobe auto-save, preview, and cache folders
+  "Adobe Premiere Pro Auto-Save",
+  "Adobe Premiere Pro Audio Previews",
+  "Adobe Premiere Pro Video Previews",
+  ...MEDIA_CACHE_DIRECTORIES,
+  ".BridgeCache",
+  // Tooling
+  "node_modules",
+  ".git",
+];
+
+/**
+ * Checks if a directory should be skipped during scans.
+ * Names are compared case-insensitively.
+ * @param name - The directory name (not a path)
+ * @param excluded - Directory names to skip, defaults to the built-in set
+ * @returns true if the directory is excluded
+ */
+export const isExcludedDirectory = (
+  name: string,
+  excluded: readonly string[] = DEFAULT_EXCLUDED_DIRECTORIES,
+): boolean => {
+  const lowerName = name.toLowerCase();
+  return excluded.some(
+    (excludedName) => excludedName.toLowerCase() === lowerName,
+  );
+};
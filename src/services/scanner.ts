@@ -1,11 +1,31 @@
-import { isMediaFile } from "../api/filesystem";
+import {
+  DEFAULT_EXCLUDED_DIRECTORIES,
+  MEDIA_CACHE_DIRECTORIES,
+  getMediaCategory,
+  getProjectApplication,
+  isExcludedDirectory,
+  isSyncConflictFile,
+  normalizeExtension,
+} from "../api/filesystem";
 import { uxp } from "../globals";
-import type { FileEntry, ScanResult } from "../types/watcher";
+import type { FileCategory } from "../api/filesystem";
+import type {
+  DirectoryEntry,
+  FileEntry,
+  ScanOptions,
+  ScanOrder,
+  ScanResult,
+} from "../types/watcher";
 
 type Folder = Awaited<
   ReturnType<typeof uxp.storage.localFileSystem.getFolder>
 >;
 type Entry = Awaited<ReturnType<NonNullable<Folder>["getEntries"]>>[number];
+type EntryMetadata = { size: number; dateModified: Date };
+
+const YIELD_INTERVAL = 50; // Directories read between yields to the event loop
+const PROGRESS_INTERVAL_MS = 250; // Minimum time between progress reports
+const METADATA_BATCH_SIZE = 64; // Concurrent getMetadata() calls per batch
 
 /**
  * Extracts the lowercase file extension from a filename.
@@ -20,92 +40,452 @@ function getExtension(filename: string): string {
 }
 
 /**
- * Calculates the relative path from a root path.
- * Normalizes path separators to forward slashes.
+ * Joins an entry name onto its parent's relative path with a forward slash.
+ * Names are used verbatim, so characters like "\\" in a macOS filename are
+ * never mistaken for separators.
  */
-function getRelativePath(absolutePath: string, rootPath: string): string {
-  let relative = absolutePath;
+function joinRelativePath(parentRelativePath: string, name: string): string {
+  return parentRelativePath ? `${parentRelativePath}/${name}` : name;
+}
 
-  if (absolutePath.startsWith(rootPath)) {
-    relative = absolutePath.slice(rootPath.length);
+/**
+ * Resolves a file's category from the built-in, enabled opt-in, and custom
+ * categories. Extensions configured in `projectApplications` that no category
+ * covers count as "externalProject" files, so they follow that opt-in.
+ */
+function getFileCategory(
+  filename: string,
+  options: ScanOptions,
+): FileCategory | null {
+  const category = getMediaCategory(
+    filename,
+    options.customCategories,
+    options.enabledCategories,
+  );
+  if (
+    category !== null ||
+    !options.enabledCategories?.includes("externalProject")
+  ) {
+    return category;
   }
 
-  // Remove leading separator
-  if (relative.startsWith("/") || relative.startsWith("\\")) {
-    relative = relative.slice(1);
+  const extension = getExtension(filename);
+  const isConfiguredProject = Object.keys(
+    options.projectApplications ?? {},
+  ).some((custom) => normalizeExtension(custom) === extension);
+  return isConfiguredProject ? "externalProject" : null;
+}
+
+/**
+ * Decides whether a file should be included under the given filter mode.
+ * "all" accepts every non-hidden file, "custom" matches the supplied extensions.
+ * "media" matches media categories, minus any the options exclude.
+ */
+function shouldIncludeFile(filename: string, options: ScanOptions): boolean {
+  switch (options.filterMode ?? "media") {
+    case "all":
+      return !filename.startsWith(".");
+    case "custom": {
+      const extension = getExtension(filename);
+      return (options.extensions ?? []).some(
+        (custom) => normalizeExtension(custom) === extension,
+      );
+    }
+    case "media": {
+      const category = getFileCategory(filename, options);
+      return (
+        category !== null && !options.excludedCategories?.includes(category)
+      );
+    }
   }
+}
 
-  // Normalize to forward slashes
-  return relative.replace(/\\/g, "/");
+/**
+ * Gets the directory names to skip. With the "cache" category enabled, the
+ * default list keeps Premiere's cache folders so their files can be found.
+ */
+function getExcludedDirectories(options: ScanOptions): readonly string[] {
+  if (options.excludedDirectories) {
+    return options.excludedDirectories;
+  }
+  if (options.enabledCategories?.includes("cache")) {
+    return DEFAULT_EXCLUDED_DIRECTORIES.filter(
+      (name) => !MEDIA_CACHE_DIRECTORIES.includes(name),
+    );
+  }
+  return DEFAULT_EXCLUDED_DIRECTORIES;
 }
 
 /**
- * Recursively scans a directory, collecting media files and subdirectories.
+ * Decides whether a subdirectory should be descended into. Besides the
+ * exclusion list, "all" mode skips hidden directories as it does hidden files.
  */
-async function scanDirectory(
-  folder: NonNullable<Folder>,
-  rootPath: string,
+function shouldScanDirectory(
+  name: string,
+  excludedDirectories: readonly string[],
+  options: ScanOptions,
+): boolean {
+  if (options.filterMode === "all" && name.startsWith(".")) {
+    return false;
+  }
+  return !isExcludedDirectory(name, excludedDirectories);
+}
+
+/**
+ * Whether any scan option depends on file metadata, which costs an extra
+ * call per file and is therefore only fetched when needed.
+ */
+function needsMetadata(options: ScanOptions): boolean {
+  return (
+    options.minSize !== undefined ||
+    options.maxSize !== undefined ||
+    options.ignoreOlderThan !== undefined ||
+    (options.scanOrder !== undefined && options.scanOrder !== "name") ||
+    options.newestFirst === true
+  );
+}
+
+/**
+ * Resolves the age filter to an absolute cutoff timestamp in milliseconds.
+ * Relative ages are measured from `now`, so one cutoff applies to a whole scan.
+ */
+function getAgeCutoff(ignoreOlderThan: Date | number, now: number): number {
+  return ignoreOlderThan instanceof Date
+    ? ignoreOlderThan.getTime()
+    : now - ignoreOlderThan;
+}
+
+/**
+ * Checks file metadata against the size limits in the scan options and the
+ * age cutoff resolved at the start of the scan.
+ */
+function passesMetadataFilters(
+  metadata: EntryMetadata,
+  options: ScanOptions,
+  ageCutoff: number | undefined,
+): boolean {
+  if (options.minSize !== undefined && metadata.size < options.minSize) {
+    return false;
+  }
+  if (options.maxSize !== undefined && metadata.size > options.maxSize) {
+    return false;
+  }
+  if (
+    ageCutoff !== undefined &&
+    new Date(metadata.dateModified).getTime() < ageCutoff
+  ) {
+    return false;
+  }
+  return true;
+}
+
+/**
+ * Natural name comparison, so "Clip 2" sorts before "Clip 10".
+ */
+function compareNames(a: string, b: string): number {
+  return a.localeCompare(b, undefined, { numeric: true, sensitivity: "base" });
+}
+
+/**
+ * Compares two files according to the requested scan order.
+ * Ties fall back to natural name order.
+ */
+function compareFiles(a: FileEntry, b: FileEntry, order: ScanOrder): number {
+  const aModified = a.modifiedAt?.getTime() ?? 0;
+  const bModified = b.modifiedAt?.getTime() ?? 0;
+  let result = 0;
+
+  switch (order) {
+    case "modified-asc":
+      result = aModified - bModified;
+      break;
+    case "modified-desc":
+      result = bModified - aModified;
+      break;
+    case "size":
+      result = (a.size ?? 0) - (b.size ?? 0);
+      break;
+    case "name":
+      break;
+  }
+
+  return result !== 0 ? result : compareNames(a.name, b.name);
+}
+
+/**
+ * Sorts files newest first across the whole scan and reorders directories so
+ * the ones holding the newest files come first, keeping parents ahead of
+ * their children. Directories without matching files keep their scan order.
+ */
+function prioritizeNewest(
   files: FileEntry[],
-  directories: string[],
-): Promise<void> {
+  directories: DirectoryEntry[],
+): DirectoryEntry[] {
+  files.sort((a, b) => compareFiles(a, b, "modified-desc"));
+
+  const byPath = new Map(
+    directories.map((directory) => [directory.relativePath, directory]),
+  );
+  const ordered = new Set<DirectoryEntry>();
+  for (const file of files) {
+    const ancestors: DirectoryEntry[] = [];
+    let directory = byPath.get(file.parentRelativePath);
+    while (directory) {
+      ancestors.push(directory);
+      directory = byPath.get(directory.parentRelativePath);
+    }
+    for (const ancestor of ancestors.reverse()) {
+      ordered.add(ancestor);
+    }
+  }
+  for (const directory of directories) {
+    ordered.add(directory);
+  }
+
+  return Array.from(ordered);
+}
+
+/**
+ * Fetches metadata for the given entries in bounded batches. Entries whose
+ * metadata can't be read, usually because they were removed after listing,
+ * resolve to null instead of failing the scan.
+ */
+async function fetchMetadata(
+  entries: Entry[],
+): Promise<(EntryMetadata | null)[]> {
+  const results: (EntryMetadata | null)[] = [];
+
+  for (let i = 0; i < entries.length; i += METADATA_BATCH_SIZE) {
+    const batch = entries.slice(i, i + METADATA_BATCH_SIZE);
+    const settled = await Promise.allSettled(
+      batch.map((entry) =>
+        (
+          entry as Entry & { getMetadata(): Promise<EntryMetadata> }
+        ).getMetadata(),
+      ),
+    );
+    for (const outcome of settled) {
+      results.push(outcome.status === "fulfilled" ? outcome.value : null);
+    }
+  }
+
+  return results;
+}
+
+/**
+ * Reads a single directory, returning its matching files and subfolders
+ * in the order they should be listed.
+ * @param relativePath - The directory's path relative to the watch root
+ */
+async function readDirectory(
+  folder: NonNullable<Folder>,
+  relativePath: string,
+  options: ScanOptions,
+  ageCutoff: number | undefined,
+): Promise<{ files: FileEntry[]; subfolders: Entry[] }> {
   const entries = await folder.getEntries();
+  const subfolders: Entry[] = [];
+  const candidates: { entry: Entry; syncConflict: boolean }[] = [];
+  const directoryFiles: FileEntry[] = [];
+  const excludedDirectories = getExcludedDirectories(options);
 
   for (const entry of entries) {
-    const entryPath = (entry as Entry & { nativePath: string }).nativePath;
-    const relativePath = getRelativePath(entryPath, rootPath);
-
     if (entry.isFolder) {
-      directories.push(relativePath);
-      await scanDirectory(
-        entry as NonNullable<Folder>,
-        rootPath,
-        files,
-        directories,
+      if (shouldScanDirectory(entry.name, excludedDirectories, options)) {
+        subfolders.push(entry);
+      }
+    } else if (shouldIncludeFile(entry.name, options)) {
+      const syncConflict = isSyncConflictFile(
+        entry.name,
+        options.syncConflictPatterns,
       );
-    } else if (isMediaFile(entry.name)) {
-      files.push({
-        path: entryPath,
+      if (syncConflict && !options.includeSyncConflicts) {
+        continue;
+      }
+      candidates.push({ entry, syncConflict });
+    }
+  }
+
+  const metadataList = needsMetadata(options)
+    ? await fetchMetadata(candidates.map(({ entry }) => entry))
+    : null;
+
+  for (const [index, { entry, syncConflict }] of candidates.entries()) {
+    const metadata = metadataList?.[index] ?? null;
+    if (metadataList) {
+      // Files whose metadata is unreadable can't be filtered or imported
+      if (!metadata || !passesMetadataFilters(metadata, options, ageCutoff)) {
+        continue;
+      }
+    }
+
+    const entryPath = (entry as Entry & { nativePath: string }).nativePath;
+    const category = getFileCategory(entry.name, options);
+    directoryFiles.push({
+      path: entryPath,
+      relativePath: joinRelativePath(relativePath, entry.name),
+      parentRelativePath: relativePath,
+      name: entry.name,
+      extension: getExtension(entry.name),
+      category,
+      isDirectory: false,
+      application:
+        getProjectApplication(entry.name, options.projectApplications) ??
+        undefined,
+      syncConflict: syncConflict || undefined,
+      size: metadata?.size,
+      modifiedAt: metadata ? new Date(metadata.dateModified) : undefined,
+    });
+  }
+
+  const { scanOrder } = options;
+  if (scanOrder) {
+    directoryFiles.sort((a, b) => compareFiles(a, b, scanOrder));
+    subfolders.sort((a, b) => compareNames(a.name, b.name));
+  }
+
+  return { files: directoryFiles, subfolders };
+}
+
+/**
+ * Walks a folder tree depth-first with an explicit stack, so deep trees can't
+ * overflow the call stack. Files in a directory are listed before its
+ * subdirectories are descended into, and control is handed back to the event
+ * loop periodically so the panel stays responsive during large scans.
+ * Progress is reported every PROGRESS_INTERVAL_MS at most, and once at the end.
+ * Directories that can't be read are recorded and their subtree is skipped.
+ * @returns false if the scan was cancelled before the whole tree was read
+ */
+async function walkFolder(
+  root: NonNullable<Folder>,
+  files: FileEntry[],
+  directories: DirectoryEntry[],
+  skippedDirectories: string[],
+  options: ScanOptions,
+): Promise<boolean> {
+  const stack: {
+    folder: NonNullable<Folder>;
+    relativePath: string;
+    parentRelativePath: string;
+  }[] = [{ folder: root, relativePath: "", parentRelativePath: "" }];
+  const startedAt = Date.now();
+  const ageCutoff =
+    options.ignoreOlderThan !== undefined
+      ? getAgeCutoff(options.ignoreOlderThan, startedAt)
+      : undefined;
+  let lastProgressAt = startedAt;
+  let directoriesRead = 0;
+
+  const reportProgress = () => {
+    lastProgressAt = Date.now();
+    options.onProgress?.({
+      filesFound: files.length,
+      directoriesFound: directories.length,
+      elapsedMs: lastProgressAt - startedAt,
+    });
+  };
+
+  while (stack.length > 0) {
+    if (options.signal?.aborted) {
+      reportProgress();
+      return false;
+    }
+
+    const { folder, relativePath, parentRelativePath } = stack.pop()!;
+    const folderPath = (folder as NonNullable<Folder> & { nativePath: string })
+      .nativePath;
+
+    let contents: Awaited<ReturnType<typeof readDirectory>>;
+    try {
+      contents = await readDirectory(folder, relativePath, options, ageCutoff);
+    } catch (error) {
+      console.warn(`Skipping unreadable directory "${folderPath}":`, error);
+      skippedDirectories.push(relativePath);
+      continue;
+    }
+
+    if (folder !== root) {
+      directories.push({
+        path: folderPath,
         relativePath,
-        name: entry.name,
-        extension: getExtension(entry.name),
-        isDirectory: false,
+        parentRelativePath,
+        name: folder.name,
       });
     }
+    files.push(...contents.files);
+
+    // Push in reverse so subfolders are popped in listing order
+    for (let i = contents.subfolders.length - 1; i >= 0; i--) {
+      const subfolder = contents.subfolders[i];
+      stack.push({
+        folder: subfolder as NonNullable<Folder>,
+        relativePath: joinRelativePath(relativePath, subfolder.name),
+        parentRelativePath: relativePath,
+      });
+    }
+
+    directoriesRead++;
+    if (directoriesRead % YIELD_INTERVAL === 0) {
+      await new Promise((resolve) => setTimeout(resolve, 0));
+    }
+    if (Date.now() - lastProgressAt >= PROGRESS_INTERVAL_MS) {
+      reportProgress();
+    }
   }
+
+  reportProgress();
+  return true;
 }
 
 /**
  * Scans a folder recursively for media files.
  * @param folder - UXP Folder object to scan
  * @param watchId - Identifier for this watch operation
- * @returns ScanResult containing all discovered media files and directories
+ * @param options - Filtering options, defaults to media files only
+ * @returns ScanResult containing all discovered media files and directories,
+ * or the files found so far if `options.signal` was aborted. Unreadable
+ * directories are listed in `skippedDirectories` instead of failing the scan
  */
 export async function scanFolder(
   folder: NonNullable<Folder>,
   watchId: string,
+  options: ScanOptions = {},
 ): Promise<ScanResult> {
   const files: FileEntry[] = [];
-  const directories: string[] = [];
-  const rootPath = (folder as NonNullable<Folder> & { nativePath: string })
-    .nativePath;
+  let directories: DirectoryEntry[] = [];
+  const skippedDirectories: string[] = [];
+
+  const completed = await walkFolder(
+    folder,
+    files,
+    directories,
+    skippedDirectories,
+    options,
+  );
 
-  await scanDirectory(folder, rootPath, files, directories);
+  if (options.newestFirst) {
+    directories = prioritizeNewest(files, directories);
+  }
 
   return {
     watchId,
     files,
     directories,
+    skippedDirectories,
     scannedAt: new Date(),
+    cancelled: !completed,
   };
 }
 
 /**
  * Opens a folder picker and scans the selected folder for media files.
  * @param watchId - Identifier for this watch operation
+ * @param options - Filtering options, defaults to media files only
  * @returns ScanResult or null if user cancels the picker
  */
 export async function pickAndScanFolder(
   watchId: string,
+  options: ScanOptions = {},
 ): Promise<ScanResult | null> {
   const folder = await uxp.storage.localFileSystem.getFolder();
 
@@ -113,5 +493,5 @@ export async function pickAndScanFolder(
     return null;
   }
 
-  return scanFolder(folder, watchId);
+  return scanFolder(folder, watchId, options);
 }
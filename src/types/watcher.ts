@@ -1,3 +1,9 @@
+import type {
+  CustomCategories,
+  FileCategory,
+  MediaCategory,
+} from "../api/filesystem";
+
 export interface WatchedFolder {
   id: string;
   path: string;
@@ -9,16 +15,64 @@ export interface WatchedFolder {
 export interface FileEntry {
   path: string;
   relativePath: string;
+  parentRelativePath: string; // Containing directory, "" for the watch root
   name: string;
   extension: string;
+  category: FileCategory | null; // null for files matched outside any category
   isDirectory: boolean;
+  application?: string; // Likely owning application of a project file
+  syncConflict?: boolean; // Set when the name matches a sync conflict pattern
+  size?: number; // Bytes, only set when a scan option required metadata
+  modifiedAt?: Date; // Only set when a scan option required metadata
+}
+
+export interface DirectoryEntry {
+  path: string;
+  relativePath: string;
+  parentRelativePath: string; // Containing directory, "" for the watch root
+  name: string;
+}
+
+export type FilterMode = "media" | "all" | "custom";
+
+export type ScanOrder = "name" | "modified-asc" | "modified-desc" | "size";
+
+export interface ScanProgress {
+  filesFound: number;
+  directoriesFound: number;
+  elapsedMs: number;
+}
+
+export interface ScanOptions {
+  filterMode?: FilterMode; // Defaults to "media"
+  extensions?: string[]; // Extensions matched when filterMode is "custom"
+  customCategories?: CustomCategories; // Extra categories treated as media
+  enabledCategories?: MediaCategory[]; // Opt-in categories, e.g. "lut"
+  excludedCategories?: FileCategory[]; // Categories to skip, e.g. "image"
+  // Extends PROJECT_APPLICATIONS. Extensions no other category covers are
+  // detected as "externalProject" files when that category is enabled.
+  projectApplications?: Record<string, string>;
+  minSize?: number; // Skip files smaller than this many bytes
+  maxSize?: number; // Skip files larger than this many bytes
+  ignoreOlderThan?: Date | number; // Absolute date, or max age in milliseconds
+  scanOrder?: ScanOrder; // Defaults to filesystem order
+  newestFirst?: boolean; // Sort all files by modification time, newest first
+  // Replaces DEFAULT_EXCLUDED_DIRECTORIES. The defaults keep the media cache
+  // folders when the "cache" category is enabled, a custom list does not.
+  excludedDirectories?: string[];
+  syncConflictPatterns?: RegExp[]; // Replaces SYNC_CONFLICT_PATTERNS
+  includeSyncConflicts?: boolean; // Keep and flag conflict copies
+  signal?: AbortSignal; // Aborting stops the scan after the current directory
+  onProgress?: (progress: ScanProgress) => void; // Periodic progress callback
 }
 
 export interface ScanResult {
   watchId: string;
   files: FileEntry[];
-  directories: string[]; // Relative paths of all subdirectories
+  directories: DirectoryEntry[]; // All subdirectories, parents first
+  skippedDirectories: string[]; // Relative paths that could not be read
   scannedAt: Date;
+  cancelled: boolean; // True if the scan was aborted and results are partial
 }
 
 export interface ImportResult {